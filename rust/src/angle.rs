@@ -1,8 +1,29 @@
+use std::fmt;
 use std::ops::{Add, Div, Mul, Sub};
 
 #[derive(Copy, Clone)]
 pub(crate) struct Angle(f64);
 
+// An error parsing a sexagesimal (degrees/minutes/seconds) coordinate string.
+#[derive(Debug)]
+pub enum ParseError {
+    // The string didn't look like D°M'S" (seconds are optional).
+    InvalidFormat,
+    // The trailing hemisphere letter wasn't one of N/S/E/W.
+    InvalidHemisphere,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidFormat => write!(f, "invalid DMS coordinate, expected e.g. 52°01'18.5\"N"),
+            ParseError::InvalidHemisphere => write!(f, "invalid hemisphere letter, expected one of N/S/E/W"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl Angle {
     pub fn deg(&self) -> f64 {
         self.0.to_degrees()
@@ -18,6 +39,57 @@ impl Angle {
         Angle(deg.to_radians())
     }
 
+    // Builds an angle from degrees/minutes/seconds, e.g. Angle::from_dms(52.0, 1.0, 18.5). Only the sign of deg
+    // is used to determine the sign of the whole angle.
+    pub fn from_dms(deg: f64, min: f64, sec: f64) -> Self {
+        let sign = if deg < 0.0 { -1.0 } else { 1.0 };
+        Angle::from_deg(sign * (deg.abs() + min / 60.0 + sec / 3600.0))
+    }
+
+    // Parses a sexagesimal coordinate such as "52°01'18.5\"N" or "8°32'06\"E". Seconds may be omitted. A
+    // leading '-' is honored, and a trailing S/W flips the sign (on top of any leading '-').
+    pub fn from_dms_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+
+        let (body, hemisphere) = match s.chars().last() {
+            Some(c) if c.is_ascii_alphabetic() => (&s[..s.len() - c.len_utf8()], Some(c.to_ascii_uppercase())),
+            _ => (s, None),
+        };
+
+        let (sign, body) = match body.trim().strip_prefix('-') {
+            Some(rest) => (-1.0, rest),
+            None => (1.0, body.trim()),
+        };
+
+        let deg_end = body.find('\u{b0}').ok_or(ParseError::InvalidFormat)?;
+        let deg: f64 = body[..deg_end]
+            .trim()
+            .parse()
+            .map_err(|_| ParseError::InvalidFormat)?;
+        let rest = &body[deg_end + '\u{b0}'.len_utf8()..];
+
+        let min_end = rest.find('\'').ok_or(ParseError::InvalidFormat)?;
+        let min: f64 = rest[..min_end]
+            .trim()
+            .parse()
+            .map_err(|_| ParseError::InvalidFormat)?;
+        let rest = rest[min_end + '\''.len_utf8()..].trim();
+
+        let sec = match rest.strip_suffix('"') {
+            Some(sec) => sec.trim().parse().map_err(|_| ParseError::InvalidFormat)?,
+            None if rest.is_empty() => 0.0,
+            None => return Err(ParseError::InvalidFormat),
+        };
+
+        let angle = Angle::from_dms(deg, min, sec) * sign;
+
+        match hemisphere {
+            None | Some('N') | Some('E') => Ok(angle),
+            Some('S') | Some('W') => Ok(angle * -1.0),
+            Some(_) => Err(ParseError::InvalidHemisphere),
+        }
+    }
+
     pub fn sin(&self) -> f64 {
         self.0.sin()
     }
@@ -63,3 +135,53 @@ impl Div<f64> for Angle {
         Angle(self.0 / rhs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_deg_close(angle: Angle, expected: f64) {
+        assert!(
+            (angle.deg() - expected).abs() < 1e-9,
+            "expected {expected}, got {}",
+            angle.deg()
+        );
+    }
+
+    #[test]
+    fn parses_north_and_east() {
+        assert_deg_close(Angle::from_dms_str("52°01'18.5\"N").unwrap(), 52.0 + 1.0 / 60.0 + 18.5 / 3600.0);
+        assert_deg_close(Angle::from_dms_str("8°32'06\"E").unwrap(), 8.0 + 32.0 / 60.0 + 6.0 / 3600.0);
+    }
+
+    #[test]
+    fn parses_south_and_west() {
+        assert_deg_close(Angle::from_dms_str("52°01'18.5\"S").unwrap(), -(52.0 + 1.0 / 60.0 + 18.5 / 3600.0));
+        assert_deg_close(Angle::from_dms_str("8°32'06\"W").unwrap(), -(8.0 + 32.0 / 60.0 + 6.0 / 3600.0));
+    }
+
+    #[test]
+    fn leading_minus_combines_with_hemisphere_letter() {
+        // A leading `-` and a S/W hemisphere letter both flip the sign, so together they cancel out.
+        assert_deg_close(Angle::from_dms_str("-8°32'06\"W").unwrap(), 8.0 + 32.0 / 60.0 + 6.0 / 3600.0);
+    }
+
+    #[test]
+    fn seconds_are_optional() {
+        assert_deg_close(Angle::from_dms_str("52°01'N").unwrap(), 52.0 + 1.0 / 60.0);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(matches!(Angle::from_dms_str("52 01 18N"), Err(ParseError::InvalidFormat)));
+        assert!(matches!(Angle::from_dms_str("52°01N"), Err(ParseError::InvalidFormat)));
+    }
+
+    #[test]
+    fn rejects_invalid_hemisphere_letter() {
+        assert!(matches!(
+            Angle::from_dms_str("52°01'18\"X"),
+            Err(ParseError::InvalidHemisphere)
+        ));
+    }
+}
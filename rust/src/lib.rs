@@ -1,13 +1,34 @@
-use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
 use angle::Angle;
-use astro::{time_of_solar_elevation, time_of_solar_noon};
+pub use angle::ParseError;
+use astro::{geocentric_latitude, sun_position, time_of_solar_elevation, time_of_solar_noon, SolarEvent};
 use julian::{JulianCentury, JulianDay};
 
 mod angle;
 mod astro;
 mod julian;
 
-enum SunTime {
+// Unlike Option<DateTime<Utc>>, this distinguishes polar night (AlwaysBelow) from polar day (AlwaysAbove)
+// instead of collapsing both into None.
+#[derive(Copy, Clone)]
+pub enum Event {
+    At(DateTime<Utc>),
+    AlwaysAbove,
+    AlwaysBelow,
+}
+
+impl Event {
+    // Matches the original Option-based API for callers that don't need the polar distinction.
+    pub fn into_option(self) -> Option<DateTime<Utc>> {
+        match self {
+            Event::At(t) => Some(t),
+            Event::AlwaysAbove | Event::AlwaysBelow => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum SunTime {
     Noon,
     Midnight,
     AstroDawn,
@@ -50,15 +71,70 @@ pub struct SunTimesC {
 const ASTRO_TWILIGHT_ELEV: f64 = -18.0;
 const NAUT_TWILIGHT_ELEV: f64 = -12.0;
 const CIVIL_TWILIGHT_ELEV: f64 = -6.0;
+// Sunrise/sunset threshold at standard conditions (10 °C, 1010 hPa): the solar disk's 16' semidiameter plus
+// ~34' of atmospheric refraction at the horizon. Kept as the default so results don't change unless a caller
+// opts into `RefractionParams`.
 const DAYTIME_ELEV: f64 = -0.833;
 
-fn time_angle(time_type: SunTime) -> Angle {
+// Mean Earth radius, used to derive the horizon dip for an elevated observer.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+// The sun's apparent angular radius, as seen from Earth.
+const SOLAR_DISK_SEMIDIAMETER_DEG: f64 = 16.0 / 60.0;
+
+// Atmospheric conditions for the Sæmundsson/Bennett refraction model, used to correct the sunrise/sunset
+// elevation threshold away from the standard-atmosphere constant baked into DAYTIME_ELEV.
+#[derive(Copy, Clone)]
+pub struct RefractionParams {
+    pub temperature_c: f64,
+    pub pressure_hpa: f64,
+}
+
+impl Default for RefractionParams {
+    // Standard atmosphere (10 °C, 1010 hPa), the same conditions DAYTIME_ELEV assumes. Evaluating the formula
+    // under them doesn't reproduce that constant exactly, so pass None instead of Some(default()) to keep
+    // today's exact results.
+    fn default() -> Self {
+        RefractionParams {
+            temperature_c: 10.0,
+            pressure_hpa: 1010.0,
+        }
+    }
+}
+
+// Atmospheric refraction in degrees at the horizon (apparent altitude 0°) under the given conditions, via the
+// Sæmundsson/Bennett formula.
+fn refraction(params: RefractionParams) -> f64 {
+    let r_arcmin = 1.02 / (10.3_f64 / 5.11).to_radians().tan();
+    r_arcmin / 60.0 * (params.pressure_hpa / 1010.0) * (283.0 / (273.0 + params.temperature_c))
+}
+
+// The fixed standard-atmosphere constant when refraction_params is None, or -(solar disk semidiameter +
+// refraction) under the given atmospheric conditions otherwise.
+fn daytime_elev(refraction_params: Option<RefractionParams>) -> f64 {
+    match refraction_params {
+        Some(params) => -(SOLAR_DISK_SEMIDIAMETER_DEG + refraction(params)),
+        None => DAYTIME_ELEV,
+    }
+}
+
+// The angle by which an observer's horizon dips below the astronomical horizon due to standing altitude_m
+// metres above sea level, e.g. on a mountain or rooftop.
+fn horizon_dip(altitude_m: f64) -> Angle {
+    Angle::from_rad((EARTH_RADIUS_M / (EARTH_RADIUS_M + altitude_m)).acos())
+}
+
+fn time_angle(time_type: SunTime, altitude_m: f64, refraction_params: Option<RefractionParams>) -> Angle {
     match time_type {
         SunTime::AstroDawn => Angle::from_deg(-90.0 + ASTRO_TWILIGHT_ELEV),
         SunTime::NautDawn => Angle::from_deg(-90.0 + NAUT_TWILIGHT_ELEV),
         SunTime::CivilDawn => return Angle::from_deg(-90.0 + CIVIL_TWILIGHT_ELEV),
-        SunTime::Sunrise => return Angle::from_deg(-90.0 + DAYTIME_ELEV),
-        SunTime::Sunset => return Angle::from_deg(90.0 - DAYTIME_ELEV),
+        SunTime::Sunrise => {
+            return Angle::from_deg(-90.0 + daytime_elev(refraction_params)) - horizon_dip(altitude_m)
+        }
+        SunTime::Sunset => {
+            return Angle::from_deg(90.0 - daytime_elev(refraction_params)) + horizon_dip(altitude_m)
+        }
         SunTime::CivilDusk => return Angle::from_deg(90.0 - CIVIL_TWILIGHT_ELEV),
         SunTime::NautDusk => return Angle::from_deg(90.0 - NAUT_TWILIGHT_ELEV),
         SunTime::AstroDusk => return Angle::from_deg(90.0 - ASTRO_TWILIGHT_ELEV),
@@ -66,12 +142,14 @@ fn time_angle(time_type: SunTime) -> Angle {
     }
 }
 
-fn get_sun_time(
+fn sun_event(
     latitude: Angle,
     longitude: Angle,
     date: NaiveDate,
     ty: SunTime,
-) -> Option<DateTime<Utc>> {
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> Event {
     // The requested midnight UTC time point in julian days. This is the mathematical baseline for all the
     // hour angles we will calculate. We have to cast to seconds first to keep the midnight part.
     let start_of_julian_century = JulianDay(2451545.0);
@@ -83,41 +161,82 @@ fn get_sun_time(
     let j_noon = j_day + a_noon;
     let t_noon = date + Duration::from(a_noon);
 
+    // The hour-angle formulas assume a geocentric latitude; correct the geodetic (surveyed) latitude for
+    // Earth's flattening before it is used for anything but solar noon, which doesn't depend on latitude.
+    let latitude = geocentric_latitude(latitude);
+
     match ty {
-        SunTime::Noon => Some(t_noon),
-        SunTime::Midnight => Some(t_noon + Duration::hours(12)),
-        ty => {
-            let angle = time_of_solar_elevation(j_noon, latitude, longitude, time_angle(ty));
-            if !angle.0.is_nan() {
-                Some(date + Duration::from(angle))
-            } else {
-                None
-            }
-        }
+        SunTime::Noon => Event::At(t_noon),
+        SunTime::Midnight => Event::At(t_noon + Duration::hours(12)),
+        ty => match time_of_solar_elevation(
+            j_noon,
+            latitude,
+            longitude,
+            time_angle(ty, altitude_m, refraction_params),
+        ) {
+            SolarEvent::At(angle) => Event::At(date + Duration::from(angle)),
+            SolarEvent::AlwaysAbove => Event::AlwaysAbove,
+            SolarEvent::AlwaysBelow => Event::AlwaysBelow,
+        },
     }
 }
 
-pub fn get_sun_times2(latitude: f64, longitude: f64, date: NaiveDate) -> SunTimes {
+// A thin Option adapter over sun_event for callers that don't need to distinguish polar day from polar night.
+fn get_sun_time(
+    latitude: Angle,
+    longitude: Angle,
+    date: NaiveDate,
+    ty: SunTime,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> Option<DateTime<Utc>> {
+    sun_event(latitude, longitude, date, ty, altitude_m, refraction_params).into_option()
+}
+
+// Like get_sun_times2, but for a single event, and distinguishing polar day from polar night instead of
+// collapsing both into None. altitude_m is the observer's height above sea level in metres, and
+// refraction_params overrides the standard-atmosphere refraction assumption for sunrise/sunset (None keeps
+// today's fixed -0.833° threshold).
+pub fn get_sun_event(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+    ty: SunTime,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> Event {
+    let lat = Angle::from_deg(latitude);
+    let lon = Angle::from_deg(longitude);
+    sun_event(lat, lon, date, ty, altitude_m, refraction_params)
+}
+
+pub fn get_sun_times2(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> SunTimes {
     let lat = Angle::from_deg(latitude);
     let lon = Angle::from_deg(longitude);
     SunTimes {
-        noon: get_sun_time(lat, lon, date, SunTime::Noon).unwrap(),
-        midnight: get_sun_time(lat, lon, date, SunTime::Midnight).unwrap(),
-        astro_dawn: get_sun_time(lat, lon, date, SunTime::AstroDawn),
-        naut_dawn: get_sun_time(lat, lon, date, SunTime::NautDawn),
-        civil_dawn: get_sun_time(lat, lon, date, SunTime::CivilDawn),
-        sunrise: get_sun_time(lat, lon, date, SunTime::Sunrise),
-        sunset: get_sun_time(lat, lon, date, SunTime::Sunset),
-        civil_dusk: get_sun_time(lat, lon, date, SunTime::CivilDusk),
-        naut_dusk: get_sun_time(lat, lon, date, SunTime::NautDusk),
-        astro_dusk: get_sun_time(lat, lon, date, SunTime::AstroDusk),
+        noon: get_sun_time(lat, lon, date, SunTime::Noon, altitude_m, refraction_params).unwrap(),
+        midnight: get_sun_time(lat, lon, date, SunTime::Midnight, altitude_m, refraction_params).unwrap(),
+        astro_dawn: get_sun_time(lat, lon, date, SunTime::AstroDawn, altitude_m, refraction_params),
+        naut_dawn: get_sun_time(lat, lon, date, SunTime::NautDawn, altitude_m, refraction_params),
+        civil_dawn: get_sun_time(lat, lon, date, SunTime::CivilDawn, altitude_m, refraction_params),
+        sunrise: get_sun_time(lat, lon, date, SunTime::Sunrise, altitude_m, refraction_params),
+        sunset: get_sun_time(lat, lon, date, SunTime::Sunset, altitude_m, refraction_params),
+        civil_dusk: get_sun_time(lat, lon, date, SunTime::CivilDusk, altitude_m, refraction_params),
+        naut_dusk: get_sun_time(lat, lon, date, SunTime::NautDusk, altitude_m, refraction_params),
+        astro_dusk: get_sun_time(lat, lon, date, SunTime::AstroDusk, altitude_m, refraction_params),
     }
 }
 
 #[no_mangle]
-pub extern "C" fn get_sun_times_r(latitude: f64, longitude: f64, tp: i64) -> SunTimesC {
+pub extern "C" fn get_sun_times_r(latitude: f64, longitude: f64, tp: i64, altitude_m: f64) -> SunTimesC {
     let date = NaiveDateTime::from_timestamp_opt(tp, 0).unwrap().date();
-    let res = get_sun_times2(latitude, longitude, date);
+    let res = get_sun_times2(latitude, longitude, date, altitude_m, None);
 
     SunTimesC {
         noon: res.noon.timestamp(),
@@ -133,6 +252,112 @@ pub extern "C" fn get_sun_times_r(latitude: f64, longitude: f64, tp: i64) -> Sun
     }
 }
 
+// The sun's (azimuth, elevation) in degrees for an arbitrary instant, rather than just the handful of crossing
+// times get_sun_times2 exposes. Azimuth is measured from south, positive towards the west.
+pub fn get_sun_position(latitude: f64, longitude: f64, datetime: DateTime<Utc>) -> (f64, f64) {
+    // The hour-angle formulas assume a geocentric latitude; correct the geodetic (surveyed) latitude for
+    // Earth's flattening the same way sun_event does.
+    let lat = geocentric_latitude(Angle::from_deg(latitude));
+    let lon = Angle::from_deg(longitude);
+
+    let start_of_julian_century = JulianDay(2451545.0);
+    let j_day = JulianCentury::from_date(datetime.date_naive()) - start_of_julian_century;
+    let day_fraction = datetime.time().num_seconds_from_midnight() as f64 / 86400.0;
+    let tp = j_day + JulianDay(day_fraction);
+
+    let (azimuth, elevation) = sun_position(tp, lat, lon);
+    (azimuth.deg(), elevation.deg())
+}
+
+// A convenience wrapper over get_sun_times2 that accepts sexagesimal (DMS) coordinates such as
+// "52°01'18.5\"N" and "8°32'06\"E", as commonly found in station catalogs and config files.
+pub fn get_sun_times_str(lat: &str, lon: &str, date: NaiveDate) -> Result<SunTimes, ParseError> {
+    let latitude = Angle::from_dms_str(lat)?.deg();
+    let longitude = Angle::from_dms_str(lon)?.deg();
+    Ok(get_sun_times2(latitude, longitude, date, 0.0, None))
+}
+
+// Finds the next/previous occurrence of ty around instant by evaluating it on each day from two days before to
+// two days after instant (in UTC) and picking the nearest candidate on the requested side. This avoids the
+// date-boundary bugs get_sun_times2 has for callers near midnight or away from UTC, since the event closest to
+// instant isn't always on instant's own UTC calendar date; far from UTC, a date's event can fall up to about
+// half a day into the neighbouring UTC date, so one day either side is not always enough.
+fn sweep_sun_event(
+    latitude: Angle,
+    longitude: Angle,
+    instant: DateTime<Utc>,
+    ty: SunTime,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+    next: bool,
+) -> Event {
+    let date = instant.date_naive();
+    let candidates = [-2, -1, 0, 1, 2]
+        .map(|offset| sun_event(latitude, longitude, date + Duration::days(offset), ty, altitude_m, refraction_params));
+
+    let mut nearest: Option<DateTime<Utc>> = None;
+    let mut polarity: Option<Event> = None;
+    for event in candidates {
+        match event {
+            Event::At(t) if next && t > instant => nearest = Some(nearest.map_or(t, |n| n.min(t))),
+            Event::At(t) if !next && t < instant => nearest = Some(nearest.map_or(t, |n| n.max(t))),
+            Event::At(_) => (),
+            always_above_or_below => polarity = Some(always_above_or_below),
+        }
+    }
+
+    match nearest {
+        Some(t) => Event::At(t),
+        // No qualifying candidate in the five-day window: report whichever day's polarity we saw (polar day /
+        // polar night) instead of falling back to a stale `At` from another day, which could violate the
+        // "strictly after/before `instant`" contract callers rely on.
+        None => polarity.unwrap_or(candidates[2]),
+    }
+}
+
+// The next occurrence of ty strictly after instant. See sweep_sun_event for the date-boundary handling this
+// provides over get_sun_times2.
+pub fn next_sun_event(
+    latitude: f64,
+    longitude: f64,
+    instant: DateTime<Utc>,
+    ty: SunTime,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> Event {
+    let lat = Angle::from_deg(latitude);
+    let lon = Angle::from_deg(longitude);
+    sweep_sun_event(lat, lon, instant, ty, altitude_m, refraction_params, true)
+}
+
+// The previous occurrence of ty strictly before instant. See sweep_sun_event for the date-boundary handling
+// this provides over get_sun_times2.
+pub fn previous_sun_event(
+    latitude: f64,
+    longitude: f64,
+    instant: DateTime<Utc>,
+    ty: SunTime,
+    altitude_m: f64,
+    refraction_params: Option<RefractionParams>,
+) -> Event {
+    let lat = Angle::from_deg(latitude);
+    let lon = Angle::from_deg(longitude);
+    sweep_sun_event(lat, lon, instant, ty, altitude_m, refraction_params, false)
+}
+
+#[repr(C)]
+pub struct SunPositionC {
+    pub azimuth: f64,
+    pub elevation: f64,
+}
+
+#[no_mangle]
+pub extern "C" fn get_sun_position_r(latitude: f64, longitude: f64, tp: i64) -> SunPositionC {
+    let datetime = Utc.from_utc_datetime(&NaiveDateTime::from_timestamp_opt(tp, 0).unwrap());
+    let (azimuth, elevation) = get_sun_position(latitude, longitude, datetime);
+    SunPositionC { azimuth, elevation }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,6 +372,141 @@ mod tests {
         NaiveDateTime::new(date, time)
     }
 
+    #[test]
+    fn sun_position_matches_noon_and_sunrise_sunset() {
+        let lat = 52.02182;
+        let lon = 8.53509;
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+
+        let noon = get_sun_time(Angle::from_deg(lat), Angle::from_deg(lon), date, SunTime::Noon, 0.0, None).unwrap();
+        let (noon_azimuth, noon_elevation) = get_sun_position(lat, lon, noon);
+        assert!(noon_azimuth.abs() < 0.1);
+        // Summer solstice at this latitude: the midday sun sits close to its yearly maximum elevation.
+        assert!((noon_elevation - 61.0).abs() < 1.0);
+
+        let sunrise =
+            get_sun_time(Angle::from_deg(lat), Angle::from_deg(lon), date, SunTime::Sunrise, 0.0, None).unwrap();
+        let (sunrise_azimuth, sunrise_elevation) = get_sun_position(lat, lon, sunrise);
+        assert!(sunrise_azimuth < 0.0);
+        assert!((sunrise_elevation - -0.833).abs() < 0.01);
+
+        let sunset =
+            get_sun_time(Angle::from_deg(lat), Angle::from_deg(lon), date, SunTime::Sunset, 0.0, None).unwrap();
+        let (sunset_azimuth, sunset_elevation) = get_sun_position(lat, lon, sunset);
+        assert!(sunset_azimuth > 0.0);
+        assert!((sunset_elevation - -0.833).abs() < 0.01);
+    }
+
+    #[test]
+    fn altitude_lowers_the_apparent_horizon() {
+        let lat = 52.02182;
+        let lon = 8.53509;
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+
+        let sea_level_sunrise = get_sun_event(lat, lon, date, SunTime::Sunrise, 0.0, None)
+            .into_option()
+            .unwrap();
+        let elevated_sunrise = get_sun_event(lat, lon, date, SunTime::Sunrise, 1000.0, None)
+            .into_option()
+            .unwrap();
+        assert!(elevated_sunrise < sea_level_sunrise);
+
+        let sea_level_sunset = get_sun_event(lat, lon, date, SunTime::Sunset, 0.0, None)
+            .into_option()
+            .unwrap();
+        let elevated_sunset = get_sun_event(lat, lon, date, SunTime::Sunset, 1000.0, None)
+            .into_option()
+            .unwrap();
+        assert!(elevated_sunset > sea_level_sunset);
+    }
+
+    #[test]
+    fn distinguishes_polar_day_from_polar_night() {
+        let lat = 78.2;
+        let lon = 15.6;
+        let summer = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let winter = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+
+        assert!(matches!(
+            get_sun_event(lat, lon, summer, SunTime::Sunrise, 0.0, None),
+            Event::AlwaysAbove
+        ));
+        assert!(matches!(
+            get_sun_event(lat, lon, winter, SunTime::Sunrise, 0.0, None),
+            Event::AlwaysBelow
+        ));
+    }
+
+    #[test]
+    fn refraction_params_shift_sunrise_away_from_the_legacy_constant() {
+        let lat = 52.02182;
+        let lon = 8.53509;
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+
+        let legacy = get_sun_event(lat, lon, date, SunTime::Sunrise, 0.0, None)
+            .into_option()
+            .unwrap();
+        let standard = get_sun_event(lat, lon, date, SunTime::Sunrise, 0.0, Some(RefractionParams::default()))
+            .into_option()
+            .unwrap();
+        let cold = get_sun_event(
+            lat,
+            lon,
+            date,
+            SunTime::Sunrise,
+            0.0,
+            Some(RefractionParams {
+                temperature_c: -20.0,
+                pressure_hpa: 1010.0,
+            }),
+        )
+        .into_option()
+        .unwrap();
+        let warm = get_sun_event(
+            lat,
+            lon,
+            date,
+            SunTime::Sunrise,
+            0.0,
+            Some(RefractionParams {
+                temperature_c: 40.0,
+                pressure_hpa: 1010.0,
+            }),
+        )
+        .into_option()
+        .unwrap();
+
+        // `Some(RefractionParams::default())` deliberately doesn't reproduce the legacy -0.833° constant exactly.
+        assert_ne!(legacy, standard);
+        // Colder, denser air refracts more, so sunrise under cold conditions appears earlier than under warm ones.
+        assert!(cold < warm);
+    }
+
+    #[test]
+    fn next_sun_event_reports_polarity_when_the_window_never_crosses() {
+        let instant = Utc.with_ymd_and_hms(2024, 6, 5, 1, 0, 0).unwrap();
+        let event = next_sun_event(66.6, 0.0, instant, SunTime::Sunrise, 0.0, None);
+        assert!(matches!(event, Event::AlwaysAbove));
+    }
+
+    #[test]
+    fn next_and_previous_sun_event_bracket_instant() {
+        let lat = 52.02182;
+        let lon = 8.53509;
+        let instant = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+
+        let next = next_sun_event(lat, lon, instant, SunTime::Sunrise, 0.0, None);
+        let prev = previous_sun_event(lat, lon, instant, SunTime::Sunrise, 0.0, None);
+
+        match (next, prev) {
+            (Event::At(n), Event::At(p)) => {
+                assert!(n > instant);
+                assert!(p < instant);
+            }
+            _ => panic!("expected a definite sunrise crossing on both sides of this latitude/instant"),
+        }
+    }
+
     #[test]
     fn it_works() {
         let dates = vec![
@@ -204,7 +564,7 @@ mod tests {
                 let tp = l.zone.from_local_datetime(d).unwrap();
                 let utc = Utc.from_utc_datetime(&tp.naive_local());
 
-                let times = get_sun_times2(lat, lon, utc.date_naive());
+                let times = get_sun_times2(lat, lon, utc.date_naive(), 0.0, None);
                 let times2 = &times;
 
                 let map_zone = |opt: Option<DateTime<_>>| {
@@ -266,7 +626,7 @@ mod tests {
         let now = Local::now();
         let utc = Utc.from_utc_datetime(&now.naive_local());
 
-        let times = get_sun_times2(lat, lon, utc.date_naive());
+        let times = get_sun_times2(lat, lon, utc.date_naive(), 0.0, None);
         let times2 = &times;
 
         let map_zone =
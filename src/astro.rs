@@ -69,13 +69,44 @@ fn equation_of_time(tp: JulianCentury) -> Angle {
     Angle::from_rad(et)
 }
 
-fn hour_angle(tp: JulianCentury, latitude: Angle, elevation: Angle) -> Angle {
+// Either the event happens at a definite point, or the requested elevation threshold is never crossed at all
+// on that day, because the sun stays permanently above or below it (polar day / polar night for that
+// threshold).
+pub(crate) enum SolarEvent<T> {
+    At(T),
+    AlwaysAbove,
+    AlwaysBelow,
+}
+
+fn hour_angle(tp: JulianCentury, latitude: Angle, elevation: Angle) -> SolarEvent<Angle> {
     // The original JavaScript code just comments to negate the return value for sunset, which is ugly, so we use
     // copysign() and negated elevation inputs to do that. Inspired by redshift/solar.c.
     let decli = sun_declination(tp);
-    let omega =
-        (elevation.cos() / (latitude.cos() * decli.cos()) - latitude.tan() * decli.tan()).acos();
-    Angle::from_rad(omega.copysign(-elevation.rad()))
+    let cos_omega =
+        elevation.cos() / (latitude.cos() * decli.cos()) - latitude.tan() * decli.tan();
+
+    // acos() is only defined on [-1, 1]; outside of that range the sun never reaches the requested elevation on
+    // this day at all, so there is no crossing time to report.
+    if cos_omega > 1.0 {
+        SolarEvent::AlwaysBelow
+    } else if cos_omega < -1.0 {
+        SolarEvent::AlwaysAbove
+    } else {
+        let omega = cos_omega.acos();
+        SolarEvent::At(Angle::from_rad(omega.copysign(-elevation.rad())))
+    }
+}
+
+// WGS84 flattening, used to correct a geodetic (surveyed) latitude into the geocentric latitude the hour-angle
+// formulas actually expect.
+const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+
+// Converts a geodetic latitude (as given on a map) into the geocentric latitude as seen from Earth's centre,
+// correcting for the planet's flattening. The two agree at the poles and the equator but differ by up to
+// ~11.5 arcminutes in between.
+pub(crate) fn geocentric_latitude(latitude: Angle) -> Angle {
+    let f = 1.0 - WGS84_FLATTENING;
+    Angle::from_rad((f * f * latitude.tan()).atan())
 }
 
 const NOON: Angle = Angle::from_rad(std::f64::consts::PI);
@@ -99,14 +130,51 @@ pub(crate) fn time_of_solar_elevation(
     latitude: Angle,
     longitude: Angle,
     elevation: Angle,
-) -> JulianDay {
+) -> SolarEvent<JulianDay> {
     // We can reuse the computation of actual noon and apply the hour angle from there like the sheet does.
-    let angle = hour_angle(noon, latitude, elevation);
+    let angle = match hour_angle(noon, latitude, elevation) {
+        SolarEvent::At(angle) => angle,
+        SolarEvent::AlwaysAbove => return SolarEvent::AlwaysAbove,
+        SolarEvent::AlwaysBelow => return SolarEvent::AlwaysBelow,
+    };
     let tp = noon - JulianDay::from(angle);
 
     // Then, with the new time point, we do a second pass to get exact equation of time and hour angle and return
     // the angle as julian days from midnight like we do for noon.
     let eq_of_time = equation_of_time(tp);
-    let angle = hour_angle(tp, latitude, elevation);
-    JulianDay::from(NOON - longitude - eq_of_time - angle)
+    let angle = match hour_angle(tp, latitude, elevation) {
+        SolarEvent::At(angle) => angle,
+        SolarEvent::AlwaysAbove => return SolarEvent::AlwaysAbove,
+        SolarEvent::AlwaysBelow => return SolarEvent::AlwaysBelow,
+    };
+    SolarEvent::At(JulianDay::from(NOON - longitude - eq_of_time - angle))
+}
+
+// The sun's true hour angle for an arbitrary instant tp, measured from the local solar meridian (negative
+// before solar noon, positive after), reusing the same day/longitude/equation-of-time convention as
+// time_of_solar_noon.
+fn true_hour_angle(tp: JulianCentury, longitude: Angle) -> Angle {
+    // J2000.0 (the epoch `tp` is measured from) falls on a noon, so the fractional part of the elapsed days sits
+    // half a day off from midnight; add it back in before taking the fraction to recover the time of day.
+    let day_fraction = (JulianDay::from(tp).0 + 0.5).rem_euclid(1.0);
+    let time_angle = Angle::from_deg(day_fraction * 360.0);
+    time_angle - NOON + longitude + equation_of_time(tp)
+}
+
+// The sun's azimuth (from south, positive towards the west) and elevation above the horizon for an arbitrary
+// instant tp, given the observer's latitude and longitude.
+pub(crate) fn sun_position(tp: JulianCentury, latitude: Angle, longitude: Angle) -> (Angle, Angle) {
+    let decli = sun_declination(tp);
+    let hour_angle = true_hour_angle(tp, longitude);
+
+    let elevation = Angle::from_rad(
+        (latitude.sin() * decli.sin() + latitude.cos() * decli.cos() * hour_angle.cos()).asin(),
+    );
+    let azimuth = Angle::from_rad(
+        hour_angle
+            .sin()
+            .atan2(hour_angle.cos() * latitude.sin() - decli.tan() * latitude.cos()),
+    );
+
+    (azimuth, elevation)
 }